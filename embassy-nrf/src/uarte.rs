@@ -4,18 +4,21 @@
 //! Lowest power consumption can only be guaranteed if the send receive futures
 //! are dropped correctly (e.g. not using `mem::forget()`).
 
+use core::cell::UnsafeCell;
 use core::future::Future;
 use core::ops::Deref;
 use core::sync::atomic::{compiler_fence, Ordering};
 use core::task::{Context, Poll};
 
 use embassy::interrupt::InterruptExt;
-use embassy::util::Signal;
+use embassy::util::{Signal, WakerRegistration};
 
 use crate::fmt::{assert, *};
 use crate::hal::pac;
+use crate::hal::ppi::{ConfigurablePpi, Ppi};
 use crate::hal::prelude::*;
 use crate::hal::target_constants::EASY_DMA_SIZE;
+use crate::hal::timer::Instance as TimerInstance;
 use crate::interrupt::Interrupt;
 use crate::{interrupt, util};
 
@@ -23,28 +26,92 @@ pub use crate::hal::uarte::Pins;
 // Re-export SVD variants to allow user to directly set values.
 pub use pac::uarte0::{baudrate::BAUDRATE_A as Baudrate, config::PARITY_A as Parity};
 
+/// Decodes a [`Baudrate`] register variant into its nominal bits-per-second.
+///
+/// The `BAUDRATE` register holds a hardware-specific encoded value rather than
+/// the bit-rate itself, so idle-timeout maths must translate the variant back
+/// into bps. Unknown encodings fall back to 1200 baud, the slowest rate.
+fn baudrate_bps(baudrate: Option<Baudrate>) -> u32 {
+    match baudrate {
+        Some(Baudrate::BAUD1200) => 1_200,
+        Some(Baudrate::BAUD2400) => 2_400,
+        Some(Baudrate::BAUD4800) => 4_800,
+        Some(Baudrate::BAUD9600) => 9_600,
+        Some(Baudrate::BAUD14400) => 14_400,
+        Some(Baudrate::BAUD19200) => 19_200,
+        Some(Baudrate::BAUD28800) => 28_800,
+        Some(Baudrate::BAUD31250) => 31_250,
+        Some(Baudrate::BAUD38400) => 38_400,
+        Some(Baudrate::BAUD56000) => 56_000,
+        Some(Baudrate::BAUD57600) => 57_600,
+        Some(Baudrate::BAUD76800) => 76_800,
+        Some(Baudrate::BAUD115200) => 115_200,
+        Some(Baudrate::BAUD230400) => 230_400,
+        Some(Baudrate::BAUD250000) => 250_000,
+        Some(Baudrate::BAUD460800) => 460_800,
+        Some(Baudrate::BAUD921600) => 921_600,
+        Some(Baudrate::BAUD1M) => 1_000_000,
+        None => 1_200,
+    }
+}
+
+/// Converts an idle timeout in bit-times at `baud` bits-per-second into ticks
+/// of the 1 MHz idle-detection timer, clamped to at least one tick so the
+/// `COMPARE` event can still fire.
+fn idle_ticks(idle_bits: u32, baud: u32) -> u32 {
+    let ticks = (idle_bits as u64 * 1_000_000 / baud as u64) as u32;
+    ticks.max(1)
+}
+
 /// Interface to the UARTE peripheral
-pub struct Uarte<T>
+pub struct Uarte<T, U>
 where
     T: Instance,
+    U: TimerInstance,
 {
     instance: T,
     irq: T::Interrupt,
+    timer: Timer<U>,
     pins: Pins,
 }
 
+/// Idle-line detection resources driving the UARTE from a TIMER over PPI.
+///
+/// The TIMER instance is owned here so its singleton cannot be re-acquired
+/// elsewhere while the driver keeps accessing the peripheral.
+struct Timer<U: TimerInstance> {
+    _instance: U,
+    _ppi_ch1: Ppi<'static, crate::hal::ppi::AnyConfigurableChannel>,
+    _ppi_ch2: Ppi<'static, crate::hal::ppi::AnyConfigurableChannel>,
+}
+
+impl<U: TimerInstance> Timer<U> {
+    fn regs(&self) -> &pac::timer0::RegisterBlock {
+        // SAFETY: the instance is owned for the lifetime of the driver, so the
+        // register block is exclusively ours to touch.
+        unsafe { &*U::ptr() }
+    }
+}
+
 pub struct State {
     tx_done: Signal<()>,
     rx_done: Signal<u32>,
 }
 
-impl<T> Uarte<T>
+impl<T, U> Uarte<T, U>
 where
     T: Instance,
+    U: TimerInstance,
 {
     /// Creates the interface to a UARTE instance.
     /// Sets the baud rate, parity and assigns the pins to the UARTE peripheral.
     ///
+    /// A `timer` and two configurable PPI channels are wired up so that
+    /// [`receive_until_idle()`](Uarte::receive_until_idle) can terminate a
+    /// reception once the line has been idle for a number of bit-times: every
+    /// received byte (`EVENTS_RXDRDY`) clears the timer, and the timer
+    /// `COMPARE` event triggers `TASKS_STOPRX`.
+    ///
     /// # Unsafe
     ///
     /// The returned API is safe unless you use `mem::forget` (or similar safe mechanisms)
@@ -54,6 +121,9 @@ where
     pub unsafe fn new(
         uarte: T,
         irq: T::Interrupt,
+        timer: U,
+        mut ppi_ch1: Ppi<'static, crate::hal::ppi::AnyConfigurableChannel>,
+        mut ppi_ch2: Ppi<'static, crate::hal::ppi::AnyConfigurableChannel>,
         mut pins: Pins,
         parity: Parity,
         baudrate: Baudrate,
@@ -93,6 +163,21 @@ where
         uarte.baudrate.write(|w| w.baudrate().variant(baudrate));
         uarte.config.write(|w| w.parity().variant(parity));
 
+        // Configure the idle-line timer. It runs freely at 1 MHz so that the
+        // `COMPARE` value can be expressed directly in microseconds of silence.
+        let timer_regs: &pac::timer0::RegisterBlock = unsafe { &*U::ptr() };
+        timer_regs.mode.write(|w| w.mode().timer());
+        timer_regs.bitmode.write(|w| w.bitmode()._32bit());
+        timer_regs.prescaler.write(|w| unsafe { w.prescaler().bits(4) });
+
+        // PPI: each received byte restarts the timeout, and a timeout stops RX.
+        ppi_ch1.set_event_endpoint(&uarte.events_rxdrdy);
+        ppi_ch1.set_task_endpoint(&timer_regs.tasks_clear);
+        ppi_ch2.set_event_endpoint(&timer_regs.events_compare[0]);
+        ppi_ch2.set_task_endpoint(&uarte.tasks_stoprx);
+        ppi_ch1.enable();
+        ppi_ch2.enable();
+
         // Enable interrupts
         uarte.events_endtx.reset();
         uarte.events_endrx.reset();
@@ -108,6 +193,11 @@ where
         Uarte {
             instance: uarte,
             irq,
+            timer: Timer {
+                _instance: timer,
+                _ppi_ch1: ppi_ch1,
+                _ppi_ch2: ppi_ch2,
+            },
             pins,
         }
     }
@@ -118,7 +208,17 @@ where
         (self.instance, self.irq, self.pins)
     }
 
-    fn enable(&mut self) {
+    /// Splits the UARTE into a transmitter and a receiver half that can be used
+    /// independently, allowing a task to transmit while another awaits a
+    /// reception. Both halves share the same [`State`] (which carries separate
+    /// `tx_done`/`rx_done` signals) and drive only their own ENDTX/ENDRX path.
+    /// The peripheral is only powered down once *both* halves are idle, which is
+    /// already handled by [`on_irq`](Uarte::on_irq).
+    pub fn split(&mut self) -> (UarteTx<'_, T, U>, UarteRx<'_, T, U>) {
+        (UarteTx { uarte: self }, UarteRx { uarte: self })
+    }
+
+    fn enable(&self) {
         trace!("enable");
         self.instance.enable.write(|w| w.enable().enabled());
     }
@@ -188,16 +288,16 @@ where
     }
 }
 
-impl<T: Instance> embassy::traits::uart::Uart for Uarte<T> {
-    type ReceiveFuture<'a> = ReceiveFuture<'a, T>;
-    type SendFuture<'a> = SendFuture<'a, T>;
+impl<T: Instance, U: TimerInstance> embassy::traits::uart::Uart for Uarte<T, U> {
+    type ReceiveFuture<'a> = ReceiveFuture<'a, T, U>;
+    type SendFuture<'a> = SendFuture<'a, T, U>;
 
     /// Sends serial data.
     ///
     /// `tx_buffer` is marked as static as per `embedded-dma` requirements.
     /// It it safe to use a buffer with a non static lifetime if memory is not
     /// reused until the future has finished.
-    fn send<'a>(&'a mut self, tx_buffer: &'a [u8]) -> SendFuture<'a, T> {
+    fn send<'a>(&'a mut self, tx_buffer: &'a [u8]) -> SendFuture<'a, T, U> {
         // Panic if TX is running which can happen if the user has called
         // `mem::forget()` on a previous future after polling it once.
         assert!(!self.tx_started());
@@ -220,7 +320,7 @@ impl<T: Instance> embassy::traits::uart::Uart for Uarte<T> {
     /// `rx_buffer` is marked as static as per `embedded-dma` requirements.
     /// It it safe to use a buffer with a non static lifetime if memory is not
     /// reused until the future has finished.
-    fn receive<'a>(&'a mut self, rx_buffer: &'a mut [u8]) -> ReceiveFuture<'a, T> {
+    fn receive<'a>(&'a mut self, rx_buffer: &'a mut [u8]) -> ReceiveFuture<'a, T, U> {
         // Panic if RX is running which can happen if the user has called
         // `mem::forget()` on a previous future after polling it once.
         assert!(!self.rx_started());
@@ -234,18 +334,105 @@ impl<T: Instance> embassy::traits::uart::Uart for Uarte<T> {
     }
 }
 
+impl<T: Instance, U: TimerInstance> Uarte<T, U> {
+    /// Receives serial data, returning early once the line has been idle for
+    /// `idle_bits` bit-times even if `rx_buffer` has not been filled.
+    ///
+    /// The idle timeout is detected in hardware by the TIMER and PPI channels
+    /// set up in [`new()`](Uarte::new): the TIMER `COMPARE` stops the receiver,
+    /// and the resulting `ENDRX`/`RXTO` reports the number of bytes actually
+    /// received.
+    pub fn receive_until_idle<'a>(
+        &'a mut self,
+        rx_buffer: &'a mut [u8],
+        idle_bits: u32,
+    ) -> ReceiveUntilIdleFuture<'a, T, U> {
+        assert!(!self.rx_started());
+
+        T::state().rx_done.reset();
+
+        ReceiveUntilIdleFuture {
+            uarte: self,
+            buf: rx_buffer,
+            idle_bits,
+        }
+    }
+}
+
+/// Transmitter half of a [`Uarte`] obtained through [`Uarte::split()`].
+pub struct UarteTx<'u, T, U>
+where
+    T: Instance,
+    U: TimerInstance,
+{
+    uarte: &'u Uarte<T, U>,
+}
+
+/// Receiver half of a [`Uarte`] obtained through [`Uarte::split()`].
+pub struct UarteRx<'u, T, U>
+where
+    T: Instance,
+    U: TimerInstance,
+{
+    uarte: &'u Uarte<T, U>,
+}
+
+impl<'u, T, U> UarteTx<'u, T, U>
+where
+    T: Instance,
+    U: TimerInstance,
+{
+    /// Sends serial data. See [`Uarte::send()`] for the safety requirements on
+    /// `tx_buffer`.
+    pub fn send<'a>(&'a mut self, tx_buffer: &'a [u8]) -> SendFuture<'a, T, U> {
+        // Panic if TX is running which can happen if the user has called
+        // `mem::forget()` on a previous future after polling it once.
+        assert!(!self.uarte.tx_started());
+
+        T::state().tx_done.reset();
+
+        SendFuture {
+            uarte: self.uarte,
+            buf: tx_buffer,
+        }
+    }
+}
+
+impl<'u, T, U> UarteRx<'u, T, U>
+where
+    T: Instance,
+    U: TimerInstance,
+{
+    /// Receives serial data. See [`Uarte::receive()`] for the safety
+    /// requirements on `rx_buffer`.
+    pub fn receive<'a>(&'a mut self, rx_buffer: &'a mut [u8]) -> ReceiveFuture<'a, T, U> {
+        // Panic if RX is running which can happen if the user has called
+        // `mem::forget()` on a previous future after polling it once.
+        assert!(!self.uarte.rx_started());
+
+        T::state().rx_done.reset();
+
+        ReceiveFuture {
+            uarte: self.uarte,
+            buf: rx_buffer,
+        }
+    }
+}
+
 /// Future for the [`Uarte::send()`] method.
-pub struct SendFuture<'a, T>
+pub struct SendFuture<'a, T, U>
 where
     T: Instance,
+    U: TimerInstance,
 {
-    uarte: &'a mut Uarte<T>,
+    uarte: &'a Uarte<T, U>,
     buf: &'a [u8],
 }
 
-impl<'a, T> Drop for SendFuture<'a, T>
+impl<'a, T, U> Drop for SendFuture<'a, T, U>
 where
     T: Instance,
+    U: TimerInstance,
 {
     fn drop(self: &mut Self) {
         if self.uarte.tx_started() {
@@ -264,9 +451,10 @@ where
     }
 }
 
-impl<'a, T> Future for SendFuture<'a, T>
+impl<'a, T, U> Future for SendFuture<'a, T, U>
 where
     T: Instance,
+    U: TimerInstance,
 {
     type Output = Result<(), embassy::traits::uart::Error>;
 
@@ -305,17 +493,19 @@ where
 }
 
 /// Future for the [`Uarte::receive()`] method.
-pub struct ReceiveFuture<'a, T>
+pub struct ReceiveFuture<'a, T, U>
 where
     T: Instance,
+    U: TimerInstance,
 {
-    uarte: &'a mut Uarte<T>,
+    uarte: &'a Uarte<T, U>,
     buf: &'a mut [u8],
 }
 
-impl<'a, T> Drop for ReceiveFuture<'a, T>
+impl<'a, T, U> Drop for ReceiveFuture<'a, T, U>
 where
     T: Instance,
+    U: TimerInstance,
 {
     fn drop(self: &mut Self) {
         if self.uarte.rx_started() {
@@ -332,9 +522,10 @@ where
     }
 }
 
-impl<'a, T> Future for ReceiveFuture<'a, T>
+impl<'a, T, U> Future for ReceiveFuture<'a, T, U>
 where
     T: Instance,
+    U: TimerInstance,
 {
     type Output = Result<(), embassy::traits::uart::Error>;
 
@@ -374,9 +565,10 @@ where
 }
 
 /// Future for the [`receive()`] method.
-impl<'a, T> ReceiveFuture<'a, T>
+impl<'a, T, U> ReceiveFuture<'a, T, U>
 where
     T: Instance,
+    U: TimerInstance,
 {
     /// Stops the ongoing reception and returns the number of bytes received.
     pub async fn stop(self) -> usize {
@@ -397,6 +589,97 @@ where
     }
 }
 
+/// Future for the [`Uarte::receive_until_idle()`] method.
+pub struct ReceiveUntilIdleFuture<'a, T, U>
+where
+    T: Instance,
+    U: TimerInstance,
+{
+    uarte: &'a mut Uarte<T, U>,
+    buf: &'a mut [u8],
+    idle_bits: u32,
+}
+
+impl<'a, T, U> Drop for ReceiveUntilIdleFuture<'a, T, U>
+where
+    T: Instance,
+    U: TimerInstance,
+{
+    fn drop(self: &mut Self) {
+        if self.uarte.rx_started() {
+            trace!("stoprx (drop)");
+
+            self.uarte.instance.events_rxstarted.reset();
+            self.uarte
+                .instance
+                .tasks_stoprx
+                .write(|w| unsafe { w.bits(1) });
+
+            util::low_power_wait_until(|| T::state().rx_done.signaled())
+        }
+    }
+}
+
+impl<'a, T, U> Future for ReceiveUntilIdleFuture<'a, T, U>
+where
+    T: Instance,
+    U: TimerInstance,
+{
+    type Output = Result<usize, embassy::traits::uart::Error>;
+
+    fn poll(self: core::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self {
+            uarte,
+            buf,
+            idle_bits,
+        } = unsafe { self.get_unchecked_mut() };
+
+        match T::state().rx_done.poll_wait(cx) {
+            Poll::Pending if !uarte.rx_started() => {
+                let ptr = buf.as_ptr();
+                let len = buf.len();
+                assert!(len <= EASY_DMA_SIZE);
+
+                uarte.enable();
+
+                // The timer counts at 1 MHz, so one microsecond per tick. Scale
+                // the requested idle bit-times into ticks using the configured
+                // baud rate. The `BAUDRATE` register stores an encoded value,
+                // not the bit-rate, so decode the variant into bits-per-second
+                // first.
+                let baud = baudrate_bps(uarte.instance.baudrate.read().baudrate().variant());
+                let ticks = idle_ticks(*idle_bits, baud);
+                uarte.timer.regs().cc[0].write(|w| unsafe { w.cc().bits(ticks) });
+                uarte.timer.regs().tasks_clear.write(|w| unsafe { w.bits(1) });
+                uarte.timer.regs().tasks_start.write(|w| unsafe { w.bits(1) });
+
+                compiler_fence(Ordering::SeqCst);
+                uarte
+                    .instance
+                    .rxd
+                    .ptr
+                    .write(|w| unsafe { w.ptr().bits(ptr as u32) });
+                uarte
+                    .instance
+                    .rxd
+                    .maxcnt
+                    .write(|w| unsafe { w.maxcnt().bits(len as _) });
+
+                trace!("startrx (until idle)");
+                uarte.instance.tasks_startrx.write(|w| unsafe { w.bits(1) });
+                while !uarte.rx_started() {} // Make sure reception has started
+
+                Poll::Pending
+            }
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(len) => {
+                uarte.timer.regs().tasks_stop.write(|w| unsafe { w.bits(1) });
+                Poll::Ready(Ok(len as usize))
+            }
+        }
+    }
+}
+
 mod private {
     pub trait Sealed {}
 }
@@ -438,3 +721,369 @@ impl Instance for pac::UARTE1 {
         &UARTE1_STATE
     }
 }
+
+/// A simple byte ring buffer over a borrowed slice.
+///
+/// Only the portions that are contiguous in memory are ever handed out, since
+/// the DMA engine and the [`AsyncBufRead`] interface both work on single
+/// slices. Callers therefore loop until the buffer drains.
+struct RingBuffer<'a> {
+    buf: &'a mut [u8],
+    start: usize,
+    end: usize,
+    empty: bool,
+}
+
+impl<'a> RingBuffer<'a> {
+    fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            buf,
+            start: 0,
+            end: 0,
+            empty: true,
+        }
+    }
+
+    /// Returns the contiguous region that can be written into next.
+    fn push_buf(&mut self) -> &mut [u8] {
+        if self.start == self.end && !self.empty {
+            // Full.
+            return &mut self.buf[..0];
+        }
+        let n = self.buf.len();
+        let end = if self.end >= self.start { n } else { self.start };
+        &mut self.buf[self.end..end]
+    }
+
+    /// Marks `n` bytes as written by the DMA.
+    fn push(&mut self, n: usize) {
+        if n != 0 {
+            self.empty = false;
+        }
+        self.end = (self.end + n) % self.buf.len();
+    }
+
+    /// Returns the contiguous region that can be read out next.
+    fn pop_buf(&mut self) -> &[u8] {
+        if self.empty {
+            return &self.buf[..0];
+        }
+        let n = self.buf.len();
+        let end = if self.end > self.start { self.end } else { n };
+        &self.buf[self.start..end]
+    }
+
+    /// Marks `n` bytes as consumed by the reader.
+    fn pop(&mut self, n: usize) {
+        self.start = (self.start + n) % self.buf.len();
+        if self.start == self.end {
+            self.empty = true;
+        }
+    }
+}
+
+/// State shared between [`BufferedUarte`] and its interrupt handler.
+struct BufferedState<'a> {
+    rx: RingBuffer<'a>,
+    rx_waker: WakerRegistration,
+    rx_started: bool,
+}
+
+/// Continuous ring-buffer reception, a standalone alternative to the one-shot
+/// [`Uarte`] driver that programs the peripheral registers itself.
+///
+/// Unlike [`Uarte::receive()`], the peripheral keeps RX DMA running into a
+/// user-supplied ring buffer so that no bytes are lost between consecutive
+/// reads. Transfers are capped at [`RX_CHUNK`] bytes and chained with the
+/// `ENDRX`→`STARTRX` short so the peripheral immediately begins the next
+/// transfer when one completes, while the `RXSTARTED` event is used to point
+/// that next transfer at the following free region. The small cap means
+/// `ENDRX` fires promptly, so the accumulated data is exposed through an
+/// [`AsyncBufRead`] interface that yields whatever is currently buffered and
+/// only suspends when the ring is empty.
+pub struct BufferedUarte<'a, T: Instance> {
+    instance: T,
+    irq: T::Interrupt,
+    pins: Pins,
+    inner: UnsafeCell<BufferedState<'a>>,
+}
+
+/// Each DMA transfer is capped to a single byte so that `ENDRX` fires as soon
+/// as anything is received. Without this cap the peripheral would only raise
+/// `ENDRX` once a whole contiguous region had filled, leaving a reader blocked
+/// whenever the sender paused mid-buffer.
+const RX_CHUNK: usize = 1;
+
+impl<'a, T: Instance> BufferedUarte<'a, T> {
+    /// Creates a continuously-receiving UARTE over `ring_buffer`.
+    ///
+    /// See [`Uarte::new()`] for the pin, parity and baudrate configuration; the
+    /// safety requirements are identical.
+    #[allow(unused_unsafe)]
+    pub unsafe fn new(
+        uarte: T,
+        irq: T::Interrupt,
+        mut pins: Pins,
+        parity: Parity,
+        baudrate: Baudrate,
+        ring_buffer: &'a mut [u8],
+    ) -> Self {
+        assert!(uarte.enable.read().enable().is_disabled());
+
+        uarte.psel.rxd.write(|w| {
+            unsafe { w.bits(pins.rxd.psel_bits()) };
+            w.connect().connected()
+        });
+
+        pins.txd.set_high().unwrap();
+        uarte.psel.txd.write(|w| {
+            unsafe { w.bits(pins.txd.psel_bits()) };
+            w.connect().connected()
+        });
+
+        uarte.psel.cts.write(|w| {
+            if let Some(ref pin) = pins.cts {
+                unsafe { w.bits(pin.psel_bits()) };
+                w.connect().connected()
+            } else {
+                w.connect().disconnected()
+            }
+        });
+
+        uarte.psel.rts.write(|w| {
+            if let Some(ref pin) = pins.rts {
+                unsafe { w.bits(pin.psel_bits()) };
+                w.connect().connected()
+            } else {
+                w.connect().disconnected()
+            }
+        });
+
+        uarte.baudrate.write(|w| w.baudrate().variant(baudrate));
+        uarte.config.write(|w| w.parity().variant(parity));
+
+        // Keep the peripheral enabled for the lifetime of the buffered driver:
+        // continuous reception defeats the point of powering it down between
+        // frames.
+        uarte.enable.write(|w| w.enable().enabled());
+
+        // Restart RX into the next free region as soon as one completes.
+        uarte.shorts.write(|w| w.endrx_startrx().enabled());
+
+        uarte.events_endrx.reset();
+        uarte.events_rxstarted.reset();
+        uarte
+            .intenset
+            .write(|w| w.endrx().set().rxstarted().set().rxto().set());
+
+        BufferedUarte {
+            instance: uarte,
+            irq,
+            pins,
+            inner: UnsafeCell::new(BufferedState {
+                rx: RingBuffer::new(ring_buffer),
+                rx_waker: WakerRegistration::new(),
+                rx_started: false,
+            }),
+        }
+    }
+
+    /// Runs `f` with the IRQ disabled so the handler cannot race on the shared
+    /// state, (re)installing the handler with a pointer to `self` each time.
+    fn with<R>(&self, f: impl FnOnce(&mut BufferedState<'a>) -> R) -> R {
+        self.irq.disable();
+        compiler_fence(Ordering::SeqCst);
+
+        self.irq.set_handler(Self::on_irq);
+        self.irq
+            .set_handler_context(self as *const _ as *mut ());
+
+        // Safety: the IRQ is disabled, so the handler cannot be accessing the
+        // state concurrently.
+        let state = unsafe { &mut *self.inner.get() };
+        let r = f(state);
+        compiler_fence(Ordering::SeqCst);
+        self.irq.enable();
+        r
+    }
+
+    /// Hands the next free ring region to the DMA and (re)starts reception.
+    fn start_rx(&self, state: &mut BufferedState<'a>) {
+        let buf = state.rx.push_buf();
+        if buf.is_empty() {
+            // Ring is full, leave the peripheral stopped until the reader
+            // drains it again.
+            return;
+        }
+
+        let ptr = buf.as_ptr();
+        let len = buf.len().min(EASY_DMA_SIZE).min(RX_CHUNK);
+
+        compiler_fence(Ordering::SeqCst);
+        self.instance
+            .rxd
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(ptr as u32) });
+        self.instance
+            .rxd
+            .maxcnt
+            .write(|w| unsafe { w.maxcnt().bits(len as _) });
+
+        if !state.rx_started {
+            // Re-arm the auto-restart short in case backpressure disabled it.
+            self.instance
+                .shorts
+                .write(|w| w.endrx_startrx().enabled());
+            trace!("buffered startrx");
+            self.instance
+                .tasks_startrx
+                .write(|w| unsafe { w.bits(1) });
+            state.rx_started = true;
+        }
+    }
+}
+
+impl<'a, T: Instance> embassy::io::AsyncBufRead for BufferedUarte<'a, T> {
+    fn poll_fill_buf(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<embassy::io::Result<&[u8]>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        this.with(|state| {
+            // Make sure DMA is running if there is room left in the ring.
+            if !state.rx_started {
+                // SAFETY: borrow juggling to let `start_rx` touch the registers
+                // while we already hold a `&mut state`.
+                let buf = state.rx.push_buf();
+                if !buf.is_empty() {
+                    this.start_rx(state);
+                }
+            }
+
+            let buf = state.rx.pop_buf();
+            if buf.is_empty() {
+                state.rx_waker.register(cx.waker());
+                Poll::Pending
+            } else {
+                // Extend the lifetime: the slice points into the ring buffer
+                // which lives as long as `self`.
+                let buf: &[u8] = unsafe { core::mem::transmute(buf) };
+                Poll::Ready(Ok(buf))
+            }
+        })
+    }
+
+    fn consume(self: core::pin::Pin<&mut Self>, amt: usize) {
+        let this = unsafe { self.get_unchecked_mut() };
+        this.with(|state| {
+            state.rx.pop(amt);
+            // Freeing up ring space may let a stalled DMA resume.
+            if !state.rx_started {
+                this.start_rx(state);
+            }
+        });
+    }
+}
+
+impl<'a, T: Instance> BufferedUarte<'a, T> {
+    /// Interrupt handler: advance the ring's write cursor on every `ENDRX`
+    /// (the DMA writes straight into the ring, nothing is copied) and keep
+    /// the `ENDRX`→`STARTRX` chain of ring-region transfers going.
+    unsafe fn on_irq(ctx: *mut ()) {
+        let this = &*(ctx as *const Self);
+        let uarte = &this.instance;
+        let state = &mut *this.inner.get();
+
+        if uarte.events_endrx.read().bits() != 0 {
+            uarte.events_endrx.reset();
+            compiler_fence(Ordering::SeqCst);
+            // Ignore the flush `ENDRX` raised by the backpressure `TASKS_STOPRX`:
+            // once `rx_started` is cleared the ring is full, so pushing here
+            // would advance `end` past `start` and clobber unread data.
+            if state.rx_started {
+                let len = uarte.rxd.amount.read().bits() as usize;
+                state.rx.push(len);
+                state.rx_waker.wake();
+            }
+        }
+
+        if uarte.events_rxstarted.read().bits() != 0 {
+            // The short already kicked off the next transfer; point it at the
+            // following free region so the ENDRX→STARTRX chain keeps going.
+            uarte.events_rxstarted.reset();
+            let buf = state.rx.push_buf();
+            if buf.is_empty() {
+                // Backpressure: the ring is full. Break the auto-restart chain
+                // and stop the in-flight transfer so the `ENDRX` short cannot
+                // relaunch DMA into the stale `rxd.ptr` and overwrite unread
+                // data. `start_rx` re-arms the short once the reader frees
+                // space.
+                uarte.shorts.write(|w| w.endrx_startrx().disabled());
+                uarte.tasks_stoprx.write(|w| w.bits(1));
+                state.rx_started = false;
+            } else {
+                let len = buf.len().min(EASY_DMA_SIZE).min(RX_CHUNK);
+                uarte
+                    .rxd
+                    .ptr
+                    .write(|w| w.ptr().bits(buf.as_ptr() as u32));
+                uarte.rxd.maxcnt.write(|w| w.maxcnt().bits(len as _));
+            }
+        }
+
+        if uarte.events_rxto.read().bits() != 0 {
+            uarte.events_rxto.reset();
+            state.rx_started = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{baudrate_bps, idle_ticks, Baudrate, RingBuffer};
+
+    #[test]
+    fn idle_ticks_decode_and_scale() {
+        // 115200 baud: the register holds an encoded value, so the decode must
+        // yield the nominal bit-rate before scaling into 1 MHz timer ticks.
+        assert_eq!(baudrate_bps(Some(Baudrate::BAUD115200)), 115_200);
+        // 10 bit-times at 115200 baud ≈ 86.8 µs.
+        assert_eq!(idle_ticks(10, 115_200), 86);
+        // A sub-tick timeout still arms the compare with at least one tick.
+        assert_eq!(idle_ticks(1, 1_000_000), 1);
+        assert_eq!(idle_ticks(100, 9_600), 10_416);
+    }
+
+    #[test]
+    fn ring_buffer_push_pop_wraps() {
+        let mut storage = [0u8; 4];
+        let mut ring = RingBuffer::new(&mut storage);
+
+        // Empty to start.
+        assert!(ring.pop_buf().is_empty());
+        assert_eq!(ring.push_buf().len(), 4);
+
+        // Fill three bytes, read two.
+        ring.push(3);
+        assert_eq!(ring.pop_buf(), &[0, 0, 0][..3]);
+        ring.pop(2);
+        // One byte left, and the free region now wraps: the tail (1 byte) is
+        // handed out before the front.
+        assert_eq!(ring.pop_buf().len(), 1);
+        assert_eq!(ring.push_buf().len(), 1);
+
+        // Fill to capacity and confirm `push_buf` reports full.
+        ring.push(1);
+        assert_eq!(ring.push_buf().len(), 2);
+        ring.push(2);
+        assert!(ring.push_buf().is_empty());
+
+        // Drain everything and the ring reports empty again. The free region
+        // is the contiguous tail from the current cursor, so it wraps rather
+        // than reporting the whole buffer at once.
+        ring.pop(1);
+        ring.pop(3);
+        assert!(ring.pop_buf().is_empty());
+        assert_eq!(ring.push_buf().len(), 2);
+    }
+}
@@ -47,6 +47,120 @@ impl<T: Instance> PLL<T> {
             p.pwr().modify(|w| w.set_postdivpd(false));
         }
     }
+
+    /// Configures the PLL to output a frequency as close as possible to
+    /// `target_hz`, searching for a valid divider set instead of requiring the
+    /// caller to precompute one.
+    ///
+    /// The search honours the same hardware constraints that [`configure()`]
+    /// asserts: `fbdiv` in `16..=520`, both post dividers in `1..=7` with
+    /// `post_div2 <= post_div1`, and a VCO frequency inside the legal
+    /// 400–1600 MHz window. Among the candidates the one whose output is closest
+    /// to `target_hz` is chosen, with ties broken towards the lowest VCO
+    /// frequency for lower power consumption.
+    ///
+    /// Returns the achieved output frequency, or [`Error::Unachievable`] if no
+    /// divider set produces a valid frequency.
+    pub fn configure_freq(&mut self, target_hz: u32) -> Result<u32, Error> {
+        let (refdiv, vco_freq, post_div1, post_div2, achieved) =
+            Self::solve(target_hz).ok_or(Error::Unachievable)?;
+        self.configure(refdiv, vco_freq, post_div1, post_div2);
+        Ok(achieved)
+    }
+
+    /// Searches the divider space for the combination closest to `target_hz`.
+    ///
+    /// Returns `(refdiv, vco_freq, post_div1, post_div2, achieved_hz)`.
+    fn solve(target_hz: u32) -> Option<(u32, u32, u8, u8, u32)> {
+        const VCO_MIN: u64 = 400_000_000;
+        const VCO_MAX: u64 = 1_600_000_000;
+
+        let target = target_hz as u64;
+        let mut best: Option<(u32, u32, u8, u8, u32)> = None;
+
+        for refdiv in 1..=2u32 {
+            if XOSC_MHZ % refdiv != 0 {
+                continue;
+            }
+            let ref_mhz = XOSC_MHZ / refdiv;
+            let ref_hz = ref_mhz as u64 * 1_000_000;
+
+            for fbdiv in 16..=520u64 {
+                let vco = ref_hz * fbdiv;
+                if vco < VCO_MIN || vco > VCO_MAX {
+                    continue;
+                }
+                // The phase-detector/FREF rule `configure` asserts is
+                // `ref_mhz <= vco_freq / 16`, which holds for every legal VCO
+                // given the 12 MHz crystal, so there is nothing further to
+                // reject here.
+
+                for post_div1 in 1..=7u8 {
+                    for post_div2 in 1..=post_div1 {
+                        let out = vco / (post_div1 as u64 * post_div2 as u64);
+                        let err = (out as i64 - target as i64).unsigned_abs();
+
+                        let better = match best {
+                            None => true,
+                            Some((_, best_vco, _, _, best_out)) => {
+                                let best_err =
+                                    (best_out as i64 - target as i64).unsigned_abs();
+                                err < best_err
+                                    || (err == best_err && vco < best_vco as u64)
+                            }
+                        };
+
+                        if better {
+                            best = Some((
+                                refdiv,
+                                vco as u32,
+                                post_div1,
+                                post_div2,
+                                out as u32,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PllSys, PLL};
+
+    #[test]
+    fn solves_system_125mhz() {
+        // The stock RP2040 system clock target is exactly reachable.
+        let (refdiv, vco, post_div1, post_div2, achieved) =
+            PLL::<PllSys>::solve(125_000_000).expect("125 MHz must be achievable");
+        assert_eq!(achieved, 125_000_000);
+        // The returned set must satisfy every hardware constraint `configure`
+        // asserts.
+        let ref_hz = 12_000_000 / refdiv;
+        assert_eq!(vco, ref_hz * (vco / ref_hz));
+        assert!((400_000_000..=1_600_000_000).contains(&vco));
+        assert!((1..=7).contains(&post_div1) && (1..=7).contains(&post_div2));
+        assert!(post_div2 <= post_div1);
+        assert_eq!(vco / (post_div1 as u32 * post_div2 as u32), achieved);
+    }
+
+    #[test]
+    fn solves_usb_48mhz() {
+        let (_, _, _, _, achieved) =
+            PLL::<PllSys>::solve(48_000_000).expect("48 MHz must be achievable");
+        assert_eq!(achieved, 48_000_000);
+    }
+}
+
+/// Errors returned when configuring a [`PLL`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// No valid divider set produces a frequency for the requested target.
+    Unachievable,
 }
 
 mod sealed {
@@ -6,35 +6,106 @@ use crate::fmt::*;
 use crate::interrupt::Interrupt;
 
 pub trait PeripheralState {
-    type Interrupt: Interrupt;
-    fn on_interrupt(&mut self);
+    /// The interrupt(s) guarding this state. This is either a single
+    /// [`Interrupt`] or a tuple of them; see [`InterruptSet`].
+    type Interrupt: InterruptSet;
+
+    /// Called from each bound interrupt. `irq` is the index of the interrupt
+    /// within `Self::Interrupt` that fired, so composite drivers (e.g. a TIMER
+    /// paired with a UARTE for idle-line RX) can tell the lines apart.
+    fn on_interrupt(&mut self, irq: usize);
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Life {
-    Created,
-    InUse,
-    Freed,
+/// A set of interrupts bound to the same [`PeripheralState`].
+///
+/// Implemented for a single [`Interrupt`] (index `0`) and for tuples of them,
+/// so that `with`/`try_free`/`Drop` can disable, install handlers on, and
+/// re-enable every line around the critical section.
+pub trait InterruptSet {
+    fn disable(&mut self);
+    fn enable(&mut self);
+    fn remove_handlers(&mut self);
+
+    /// Installs a handler on every interrupt in the set. Each handler passes
+    /// its own index to [`PeripheralState::on_interrupt`].
+    fn set_handlers<S>(&mut self, ctx: *mut ())
+    where
+        S: PeripheralState<Interrupt = Self>,
+        Self: Sized;
 }
 
+unsafe fn dispatch<S: PeripheralState, const N: usize>(ctx: *mut ()) {
+    // Safety: it's OK to get a &mut to the state, since
+    // - We're in the IRQ, no one else can preempt us.
+    // - We can't have preempted a with() call because the irqs are disabled
+    //   during it.
+    let state = &mut *(ctx as *mut S);
+    state.on_interrupt(N);
+}
+
+impl<I: Interrupt> InterruptSet for I {
+    fn disable(&mut self) {
+        Interrupt::disable(self);
+    }
+
+    fn enable(&mut self) {
+        Interrupt::enable(self);
+    }
+
+    fn remove_handlers(&mut self) {
+        self.remove_handler();
+    }
+
+    fn set_handlers<S>(&mut self, ctx: *mut ())
+    where
+        S: PeripheralState<Interrupt = Self>,
+    {
+        self.set_handler(dispatch::<S, 0>, ctx);
+    }
+}
+
+macro_rules! impl_interrupt_set {
+    ($($n:tt: $t:ident),+) => {
+        impl<$($t: Interrupt),+> InterruptSet for ($($t,)+) {
+            fn disable(&mut self) {
+                $(Interrupt::disable(&mut self.$n);)+
+            }
+
+            fn enable(&mut self) {
+                $(Interrupt::enable(&mut self.$n);)+
+            }
+
+            fn remove_handlers(&mut self) {
+                $(self.$n.remove_handler();)+
+            }
+
+            fn set_handlers<S>(&mut self, ctx: *mut ())
+            where
+                S: PeripheralState<Interrupt = Self>,
+            {
+                $(self.$n.set_handler(dispatch::<S, $n>, ctx);)+
+            }
+        }
+    };
+}
+
+impl_interrupt_set!(0: I0, 1: I1);
+impl_interrupt_set!(0: I0, 1: I1, 2: I2);
+impl_interrupt_set!(0: I0, 1: I1, 2: I2, 3: I3);
+
 pub struct PeripheralMutex<S: PeripheralState> {
-    life: Life,
-    state: MaybeUninit<UnsafeCell<S>>,
-    irq: MaybeUninit<S::Interrupt>,
+    inner: Option<(UnsafeCell<S>, S::Interrupt)>,
     not_send: PhantomData<*mut ()>,
 }
 
 impl<S: PeripheralState> PeripheralMutex<S> {
     pub fn new(state: S, irq: S::Interrupt) -> Self {
         Self {
-            life: Created,
             inner: Some((UnsafeCell::new(state), irq)),
             not_send: PhantomData,
         }
     }
 
-    fn setup(self: Pin<&mut Self>) {}
-
     pub fn with<R>(self: Pin<&mut Self>, f: impl FnOnce(&mut S, &mut S::Interrupt) -> R) -> R {
         let this = unsafe { self.get_unchecked_mut() };
         let (state, irq) = unwrap!(this.inner.as_mut());
@@ -42,18 +113,9 @@ impl<S: PeripheralState> PeripheralMutex<S> {
         irq.disable();
         compiler_fence(Ordering::SeqCst);
 
-        irq.set_handler(
-            |p| {
-                // Safety: it's OK to get a &mut to the state, since
-                // - We're in the IRQ, no one else can't preempt us
-                // - We can't have preempted a with() call because the irq is disabled during it.
-                let state = unsafe { &mut *(p as *mut S) };
-                state.on_interrupt();
-            },
-            state.get() as *mut (),
-        );
-
-        // Safety: it's OK to get a &mut to the state, since the irq is disabled.
+        irq.set_handlers::<S>(state.get() as *mut ());
+
+        // Safety: it's OK to get a &mut to the state, since the irqs are disabled.
         let state = unsafe { &mut *state.get() };
 
         let r = f(state, irq);
@@ -66,9 +128,9 @@ impl<S: PeripheralState> PeripheralMutex<S> {
 
     pub fn try_free(self: Pin<&mut Self>) -> Option<(S, S::Interrupt)> {
         let this = unsafe { self.get_unchecked_mut() };
-        this.inner.take().map(|(state, irq)| {
+        this.inner.take().map(|(state, mut irq)| {
             irq.disable();
-            irq.remove_handler();
+            irq.remove_handlers();
             (state.into_inner(), irq)
         })
     }
@@ -80,9 +142,9 @@ impl<S: PeripheralState> PeripheralMutex<S> {
 
 impl<S: PeripheralState> Drop for PeripheralMutex<S> {
     fn drop(&mut self) {
-        if let Some((state, irq)) = &mut self.inner {
+        if let Some((_state, irq)) = &mut self.inner {
             irq.disable();
-            irq.remove_handler();
+            irq.remove_handlers();
         }
     }
 }